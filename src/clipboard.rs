@@ -0,0 +1,111 @@
+use copypasta::ClipboardProvider;
+
+/// System clipboard access handed to keyboard handlers.
+///
+/// Wraps the platform clipboard and, on windowing systems that expose one (X11
+/// in particular), the primary selection, so a `press_key` handler reacting to
+/// Ctrl+C/Ctrl+V can move text in and out of the surface the user expects. The
+/// primary-selection calls fall back to the standard clipboard where the
+/// platform has no separate selection.
+///
+/// Each context is opened lazily on first use and cached, so constructing a
+/// `Clipboard` never fails or blocks — an app that never copies or pastes runs
+/// fine on headless, Wayland, or other non-X11 surfaces where opening a context
+/// would error.
+pub struct Clipboard {
+    standard: Option<copypasta::ClipboardContext>,
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "android", target_os = "ios"))
+    ))]
+    primary:
+        Option<copypasta::x11_clipboard::X11ClipboardContext<copypasta::x11_clipboard::Primary>>,
+}
+
+impl Clipboard {
+    pub fn new() -> Self {
+        Clipboard {
+            standard: None,
+            #[cfg(all(
+                unix,
+                not(any(target_os = "macos", target_os = "android", target_os = "ios"))
+            ))]
+            primary: None,
+        }
+    }
+
+    /// The standard clipboard context, opened on first use. Returns `None` if
+    /// the platform cannot provide one.
+    fn standard(&mut self) -> Option<&mut copypasta::ClipboardContext> {
+        if self.standard.is_none() {
+            self.standard = copypasta::ClipboardContext::new().ok();
+        }
+        self.standard.as_mut()
+    }
+
+    /// The primary-selection context, opened on first use. Returns `None` if
+    /// the platform cannot provide one.
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "android", target_os = "ios"))
+    ))]
+    fn primary(
+        &mut self,
+    ) -> Option<&mut copypasta::x11_clipboard::X11ClipboardContext<copypasta::x11_clipboard::Primary>>
+    {
+        if self.primary.is_none() {
+            self.primary = copypasta::x11_clipboard::X11ClipboardContext::new().ok();
+        }
+        self.primary.as_mut()
+    }
+
+    /// Current contents of the standard clipboard, or `None` if it is empty,
+    /// holds non-text data, or the platform has no clipboard.
+    pub fn get_contents(&mut self) -> Option<String> {
+        self.standard()?.get_contents().ok()
+    }
+
+    /// Replace the standard clipboard contents.
+    pub fn set_contents(&mut self, contents: String) {
+        if let Some(standard) = self.standard() {
+            let _ = standard.set_contents(contents);
+        }
+    }
+
+    /// Current contents of the primary selection, falling back to the standard
+    /// clipboard on platforms without one.
+    pub fn get_primary(&mut self) -> Option<String> {
+        #[cfg(all(
+            unix,
+            not(any(target_os = "macos", target_os = "android", target_os = "ios"))
+        ))]
+        {
+            return self.primary()?.get_contents().ok();
+        }
+        #[allow(unreachable_code)]
+        self.get_contents()
+    }
+
+    /// Replace the primary selection, falling back to the standard clipboard on
+    /// platforms without one.
+    pub fn set_primary(&mut self, contents: String) {
+        #[cfg(all(
+            unix,
+            not(any(target_os = "macos", target_os = "android", target_os = "ios"))
+        ))]
+        {
+            if let Some(primary) = self.primary() {
+                let _ = primary.set_contents(contents);
+            }
+            return;
+        }
+        #[allow(unreachable_code)]
+        self.set_contents(contents);
+    }
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
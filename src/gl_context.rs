@@ -0,0 +1,150 @@
+use glium::glutin;
+use glutin::dpi::LogicalSize;
+use glutin::event_loop::{EventLoop, EventLoopBuilder};
+use glutin::window::{Fullscreen, WindowBuilder};
+use glutin::ContextBuilder;
+
+/// Declarative description of the window a `SingleWindowApp` wants.
+///
+/// Consumed by [`GLContext::new`] to build the underlying glutin window and
+/// glium display, so an app can state its window shape up front instead of
+/// inheriting hard-coded defaults. Construct it with `WindowConfig::default()`
+/// and override fields through the chained setters.
+pub struct WindowConfig {
+    pub title: String,
+    pub logical_size: LogicalSize<f64>,
+    pub fullscreen: bool,
+    pub decorations: bool,
+    pub transparent: bool,
+    pub vsync: bool,
+    pub resizable: bool,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        WindowConfig {
+            title: "single-window-app".to_string(),
+            logical_size: LogicalSize::new(1024.0, 768.0),
+            fullscreen: false,
+            decorations: true,
+            transparent: false,
+            vsync: true,
+            resizable: true,
+        }
+    }
+}
+
+impl WindowConfig {
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn logical_size(mut self, logical_size: LogicalSize<f64>) -> Self {
+        self.logical_size = logical_size;
+        self
+    }
+
+    pub fn fullscreen(mut self, fullscreen: bool) -> Self {
+        self.fullscreen = fullscreen;
+        self
+    }
+
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    pub fn vsync(mut self, vsync: bool) -> Self {
+        self.vsync = vsync;
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+}
+
+/// The event loop and glium display handed to `SingleWindowApp::run_with`.
+///
+/// The `ViewUpdate` parameter is the user-event type the state thread pushes
+/// through the loop's `EventLoopProxy`.
+pub struct GLContext<ViewUpdate: 'static> {
+    pub event_loop: EventLoop<ViewUpdate>,
+    pub display: glium::Display,
+    pub starting_scale_factor: f64,
+}
+
+impl<ViewUpdate: 'static> GLContext<ViewUpdate> {
+    /// Build the window and OpenGL context described by `config`.
+    pub fn new(config: WindowConfig) -> Self {
+        let event_loop = EventLoopBuilder::<ViewUpdate>::with_user_event().build();
+
+        let window_builder = WindowBuilder::new()
+            .with_title(config.title)
+            .with_inner_size(config.logical_size)
+            .with_decorations(config.decorations)
+            .with_transparent(config.transparent)
+            .with_resizable(config.resizable)
+            .with_fullscreen(if config.fullscreen {
+                Some(Fullscreen::Borderless(None))
+            } else {
+                None
+            });
+
+        let context_builder = ContextBuilder::new().with_vsync(config.vsync);
+
+        let display = glium::Display::new(window_builder, context_builder, &event_loop)
+            .expect("failed to create glium display");
+
+        let starting_scale_factor = display.gl_window().window().scale_factor();
+
+        GLContext {
+            event_loop,
+            display,
+            starting_scale_factor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config() {
+        let config = WindowConfig::default();
+        assert_eq!(config.title, "single-window-app");
+        assert_eq!(config.logical_size, LogicalSize::new(1024.0, 768.0));
+        assert!(!config.fullscreen);
+        assert!(config.decorations);
+        assert!(!config.transparent);
+        assert!(config.vsync);
+        assert!(config.resizable);
+    }
+
+    #[test]
+    fn builder_overrides_every_field() {
+        let config = WindowConfig::default()
+            .title("demo")
+            .logical_size(LogicalSize::new(640.0, 480.0))
+            .fullscreen(true)
+            .decorations(false)
+            .transparent(true)
+            .vsync(false)
+            .resizable(false);
+        assert_eq!(config.title, "demo");
+        assert_eq!(config.logical_size, LogicalSize::new(640.0, 480.0));
+        assert!(config.fullscreen);
+        assert!(!config.decorations);
+        assert!(config.transparent);
+        assert!(!config.vsync);
+        assert!(!config.resizable);
+    }
+}
@@ -1,6 +1,7 @@
-pub mod font;
+pub mod clipboard;
 pub mod gl_context;
 
+use clipboard::Clipboard;
 use gl_context::GLContext;
 use glium::glutin;
 use glutin::dpi::{LogicalPosition, LogicalSize};
@@ -10,29 +11,117 @@ pub enum AppState {
     Off,
 }
 
+/// A scroll event normalized across the two `glutin` delta representations.
+///
+/// Wheel hardware reports discrete clicks as `MouseScrollDelta::LineDelta` while
+/// trackpads report high-resolution motion as `MouseScrollDelta::PixelDelta`; we
+/// surface both in a single struct so a view can drive notched scrolling from
+/// `lines` and momentum scrolling from `pixels` without caring which device was
+/// used. The field that does not correspond to the incoming event is left zero.
+pub struct ScrollDelta {
+    pub lines: (f32, f32),
+    pub pixels: LogicalPosition<f64>,
+    pub phase: glutin::event::TouchPhase,
+}
+
+impl ScrollDelta {
+    /// Normalize a raw `MouseScrollDelta` into a `ScrollDelta`, converting a
+    /// `PixelDelta`'s physical position into logical space at `scale_factor`.
+    /// The field that does not correspond to `delta`'s variant is left zero.
+    fn from_mouse_wheel(
+        delta: glutin::event::MouseScrollDelta,
+        phase: glutin::event::TouchPhase,
+        scale_factor: f64,
+    ) -> Self {
+        match delta {
+            glutin::event::MouseScrollDelta::LineDelta(x, y) => ScrollDelta {
+                lines: (x, y),
+                pixels: LogicalPosition::new(0.0, 0.0),
+                phase,
+            },
+            glutin::event::MouseScrollDelta::PixelDelta(physical_position) => ScrollDelta {
+                lines: (0.0, 0.0),
+                pixels: LogicalPosition::from_physical(physical_position, scale_factor),
+                phase,
+            },
+        }
+    }
+}
+
+/// Cursor control over the live window, handed to the pointer handlers.
+///
+/// Borrows the glutin window for the duration of a single event callback and
+/// forwards directly to its cursor calls, so a handler can request an I-beam
+/// over a text region or hide and grab the pointer for a drag interaction.
+pub struct Cursor<'a> {
+    window: &'a glutin::window::Window,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn set_icon(&self, icon: glutin::window::CursorIcon) {
+        self.window.set_cursor_icon(icon);
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.window.set_cursor_visible(visible);
+    }
+
+    pub fn set_grab(&self, mode: glutin::window::CursorGrabMode) {
+        let _ = self.window.set_cursor_grab(mode);
+    }
+}
+
+/// How the event loop decides when to repaint.
+///
+/// `Continuous` wakes the loop on a timer derived from `target_fps` and keeps
+/// requesting frames, which animated apps and games need; `Reactive` parks the
+/// loop with `ControlFlow::Wait` and only repaints in response to input or a
+/// `ViewUpdate` from the state thread, which lets a battery-sensitive UI stay
+/// idle.
+pub enum FramePacing {
+    Continuous { target_fps: f64 },
+    Reactive,
+}
+
 pub trait SingleWindowApp {
     type ViewUpdate;
     type State;
 
     fn render(&self, display: &mut glium::Display);
 
+    fn frame_pacing(&self) -> FramePacing;
+
     fn receive(&mut self, message: Self::ViewUpdate);
 
-    fn press_key(&mut self, virtual_key: glium::glutin::event::VirtualKeyCode);
+    fn press_key(
+        &mut self,
+        virtual_key: glium::glutin::event::VirtualKeyCode,
+        clipboard: &mut Clipboard,
+    );
+
+    fn receive_char(&mut self, c: char);
 
     fn set_focus(&mut self, focus: bool);
 
-    fn move_cursor(&mut self, new_position: LogicalPosition<f64>);
+    fn move_cursor(&mut self, new_position: LogicalPosition<f64>, cursor: &Cursor);
+
+    fn scroll(&mut self, delta: ScrollDelta);
 
-    fn press_mouse(&mut self, button: glutin::event::MouseButton);
+    fn press_mouse(&mut self, button: glutin::event::MouseButton, cursor: &Cursor);
 
-    fn release_mouse(&mut self, button: glutin::event::MouseButton);
+    fn release_mouse(&mut self, button: glutin::event::MouseButton, cursor: &Cursor);
 
     fn change_modifiers(&mut self, modifiers: glutin::event::ModifiersState);
 
     fn resize(&mut self, new_size: LogicalSize<f64>);
 
-    fn release_key(&mut self, virtual_key: glium::glutin::event::VirtualKeyCode);
+    fn rescale(&mut self, scale_factor: f64, new_size: LogicalSize<f64>);
+
+    fn release_key(
+        &mut self,
+        virtual_key: glium::glutin::event::VirtualKeyCode,
+        clipboard: &mut Clipboard,
+    );
 
     fn initial_state() -> Self::State;
 
@@ -53,6 +142,12 @@ pub trait SingleWindowApp {
         } = gl_context;
         let mut scale_factor = starting_scale_factor;
 
+        let mut clipboard = Clipboard::new();
+
+        // Paint once before parking the loop so a reactive app opens to a
+        // rendered frame instead of a blank window waiting on the first input.
+        display.gl_window().window().request_redraw();
+
         let event_loop_proxy = event_loop.create_proxy();
 
         std::thread::spawn(move || {
@@ -65,16 +160,26 @@ pub trait SingleWindowApp {
         });
 
         event_loop.run(move |event, _event_loop_window_target, control_flow| {
-            // Rendering
-            self.render(&mut display);
-
-            // By default, just wait until the next frame to render
-            let next_frame_time =
-                std::time::Instant::now() + std::time::Duration::from_nanos(16_666_667);
-            *control_flow = glutin::event_loop::ControlFlow::WaitUntil(next_frame_time);
+            // Pace the loop according to the app's chosen mode: animated apps
+            // wake on a timer, reactive apps park until the next input or
+            // `ViewUpdate` arrives.
+            match self.frame_pacing() {
+                FramePacing::Continuous { target_fps } => {
+                    let frame_nanos = (1_000_000_000.0 / target_fps) as u64;
+                    let next_frame_time = std::time::Instant::now()
+                        + std::time::Duration::from_nanos(frame_nanos);
+                    *control_flow = glutin::event_loop::ControlFlow::WaitUntil(next_frame_time);
+                }
+                FramePacing::Reactive => {
+                    *control_flow = glutin::event_loop::ControlFlow::Wait;
+                }
+            }
 
             // Event processing
             match event {
+                glutin::event::Event::RedrawRequested(_) => {
+                    self.render(&mut display);
+                }
                 glutin::event::Event::WindowEvent {
                     event: window_event,
                     ..
@@ -88,51 +193,92 @@ pub trait SingleWindowApp {
                     glutin::event::WindowEvent::KeyboardInput { input, .. } => {
                         if let Some(virtual_key) = input.virtual_keycode {
                             match input.state {
-                                glutin::event::ElementState::Pressed => self.press_key(virtual_key),
+                                glutin::event::ElementState::Pressed => {
+                                    self.press_key(virtual_key, &mut clipboard)
+                                }
                                 glutin::event::ElementState::Released => {
-                                    self.release_key(virtual_key)
+                                    self.release_key(virtual_key, &mut clipboard)
                                 }
                             }
-                            self.press_key(virtual_key);
                         }
+                        display.gl_window().window().request_redraw();
+                    }
+                    glutin::event::WindowEvent::ReceivedCharacter(c) => {
+                        self.receive_char(c);
+                        display.gl_window().window().request_redraw();
                     }
                     glutin::event::WindowEvent::Focused(b) => {
                         self.set_focus(b);
+                        display.gl_window().window().request_redraw();
                     }
                     glutin::event::WindowEvent::Resized(new_physical_size) => {
                         self.resize(LogicalSize::from_physical(new_physical_size, scale_factor));
+                        display.gl_window().window().request_redraw();
                     }
                     glutin::event::WindowEvent::CursorMoved {
                         position: physical_position,
                         ..
                     } => {
-                        self.move_cursor(LogicalPosition::from_physical(
-                            physical_position,
-                            scale_factor,
-                        ));
+                        let gl_window = display.gl_window();
+                        let cursor = Cursor {
+                            window: gl_window.window(),
+                        };
+                        self.move_cursor(
+                            LogicalPosition::from_physical(physical_position, scale_factor),
+                            &cursor,
+                        );
+                        gl_window.window().request_redraw();
+                    }
+                    glutin::event::WindowEvent::MouseWheel { delta, phase, .. } => {
+                        self.scroll(ScrollDelta::from_mouse_wheel(delta, phase, scale_factor));
+                        display.gl_window().window().request_redraw();
                     }
                     glutin::event::WindowEvent::ModifiersChanged(new_modifiers) => {
                         self.change_modifiers(new_modifiers);
                     }
-                    glutin::event::WindowEvent::MouseInput { state, button, .. } => match state {
-                        glutin::event::ElementState::Pressed => {
-                            self.press_mouse(button);
-                        }
-                        glutin::event::ElementState::Released => {
-                            self.release_mouse(button);
+                    glutin::event::WindowEvent::MouseInput { state, button, .. } => {
+                        let gl_window = display.gl_window();
+                        let cursor = Cursor {
+                            window: gl_window.window(),
+                        };
+                        match state {
+                            glutin::event::ElementState::Pressed => {
+                                self.press_mouse(button, &cursor);
+                            }
+                            glutin::event::ElementState::Released => {
+                                self.release_mouse(button, &cursor);
+                            }
                         }
-                    },
+                        gl_window.window().request_redraw();
+                    }
                     glutin::event::WindowEvent::ScaleFactorChanged {
                         scale_factor: new_scale_factor,
-                        ..
+                        new_inner_size,
                     } => {
                         scale_factor = new_scale_factor;
+                        self.rescale(
+                            new_scale_factor,
+                            LogicalSize::from_physical(*new_inner_size, new_scale_factor),
+                        );
+                        display.gl_window().window().request_redraw();
                     }
                     other_window_event => {
                         // For debugging, delete before release
                         println!("Other window event: {:?}", other_window_event);
                     }
                 },
+                glutin::event::Event::MainEventsCleared => {
+                    // In continuous mode the `WaitUntil` wake surfaces here, not
+                    // as a redraw request, so drive the next frame from the timer
+                    // by asking for one explicitly.
+                    if let FramePacing::Continuous { .. } = self.frame_pacing() {
+                        display.gl_window().window().request_redraw();
+                    }
+                }
+                glutin::event::Event::UserEvent(view_update) => {
+                    self.receive(view_update);
+                    display.gl_window().window().request_redraw();
+                }
                 other_glutin_event => {
                     // For debugging, delete before release
                     println!("Other glutin event: {:?}", other_glutin_event);
@@ -141,3 +287,34 @@ pub trait SingleWindowApp {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_delta_populates_lines_and_zeroes_pixels() {
+        let scroll = ScrollDelta::from_mouse_wheel(
+            glutin::event::MouseScrollDelta::LineDelta(1.0, -2.0),
+            glutin::event::TouchPhase::Moved,
+            2.0,
+        );
+        assert_eq!(scroll.lines, (1.0, -2.0));
+        assert_eq!(scroll.pixels, LogicalPosition::new(0.0, 0.0));
+        assert_eq!(scroll.phase, glutin::event::TouchPhase::Moved);
+    }
+
+    #[test]
+    fn pixel_delta_populates_pixels_and_zeroes_lines() {
+        let scroll = ScrollDelta::from_mouse_wheel(
+            glutin::event::MouseScrollDelta::PixelDelta(glutin::dpi::PhysicalPosition::new(
+                30.0, 60.0,
+            )),
+            glutin::event::TouchPhase::Ended,
+            2.0,
+        );
+        assert_eq!(scroll.lines, (0.0, 0.0));
+        assert_eq!(scroll.pixels, LogicalPosition::new(15.0, 30.0));
+        assert_eq!(scroll.phase, glutin::event::TouchPhase::Ended);
+    }
+}